@@ -0,0 +1,59 @@
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Poll the Redfish `ThresholdSensors` of one or more BMCs and render their
+/// power and thermal readings in a terminal UI.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct Args {
+    /// BMC IP addresses (or hostnames) to poll.
+    #[arg(required = true)]
+    pub ips: Vec<String>,
+
+    /// Path to the TOML config describing which sensors to display.
+    #[arg(short, long, default_value = "config.toml")]
+    pub config: PathBuf,
+
+    /// Run without the alternate-screen TUI, streaming readings instead.
+    #[arg(long)]
+    pub no_tui: bool,
+
+    /// Headless output format. Implies `--no-tui`.
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Port for the `--output prometheus` `/metrics` endpoint.
+    #[arg(long, default_value_t = 9300)]
+    pub port: u16,
+
+    /// Write logs to this file (request failures, re-auth events, parse errors).
+    #[arg(long)]
+    pub log_to: Option<PathBuf>,
+
+    /// Increase log verbosity; repeat for more (warn -> info -> debug -> trace).
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+/// How headless mode emits readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per poll, per host, to stdout (there is no file output).
+    Json,
+    /// CSV rows (with a header) to stdout (there is no file output).
+    Csv,
+    /// A Prometheus text-format `/metrics` HTTP endpoint.
+    Prometheus,
+}
+
+impl Args {
+    /// Whether the tool should run headless rather than in the TUI.
+    pub fn headless(&self) -> bool {
+        self.no_tui || self.output.is_some()
+    }
+
+    /// The resolved headless output format, defaulting to JSON.
+    pub fn format(&self) -> OutputFormat {
+        self.output.unwrap_or(OutputFormat::Json)
+    }
+}
@@ -0,0 +1,101 @@
+use crate::credentials::CredentialConfig;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Top-level monitor configuration, loaded from a TOML file.
+///
+/// Rather than hard-coding a fixed set of sensor fields, the set of sensors
+/// to display is driven entirely by the `[[sensors]]` table array, so one
+/// binary can monitor heterogeneous hardware whose Redfish `Sensors[]` expose
+/// different names.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppConfig {
+    /// Redfish collection, relative to `https://<ip>`, whose `Sensors[]` array
+    /// is polled each tick.
+    #[serde(default = "default_collection")]
+    pub collection: String,
+
+    /// Sensors to display, in render order.
+    pub sensors: Vec<SensorConfig>,
+
+    /// BMC login credentials, per-host or via a default block.
+    #[serde(default)]
+    pub credentials: CredentialConfig,
+}
+
+/// A single sensor to pull out of the Redfish `Sensors[]` array.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SensorConfig {
+    /// Value of the Redfish sensor's `Name` field to match against.
+    pub name: String,
+
+    /// Human-readable label shown in the UI.
+    pub label: String,
+
+    /// Unit suffix appended after the reading (e.g. `W`, `°C`).
+    #[serde(default)]
+    pub unit: String,
+}
+
+fn default_collection() -> String {
+    "/redfish/v1/Chassis/1/ThresholdSensors".to_string()
+}
+
+impl AppConfig {
+    /// Read and parse the config at `path`.
+    pub async fn load(path: &Path) -> Result<Self> {
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("reading config {}", path.display()))?;
+        let config: AppConfig =
+            toml::from_str(&text).with_context(|| format!("parsing config {}", path.display()))?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sensors_and_credentials() {
+        let toml = r#"
+            collection = "/redfish/v1/Chassis/2/Sensors"
+
+            [credentials.default]
+            username = "admin"
+            password = "admin"
+
+            [credentials.hosts."10.0.0.5"]
+            username = "operator"
+            password = "s3cret"
+
+            [[sensors]]
+            name = "PSU1_PIN"
+            label = "PSU"
+            unit = "W"
+
+            [[sensors]]
+            name = "CPU0_Temp"
+            label = "CPU 0"
+        "#;
+
+        let config: AppConfig = toml::from_str(toml).expect("config parses");
+        assert_eq!(config.collection, "/redfish/v1/Chassis/2/Sensors");
+        assert_eq!(config.sensors.len(), 2);
+        assert_eq!(config.sensors[0].name, "PSU1_PIN");
+        assert_eq!(config.sensors[0].unit, "W");
+        // `unit` defaults to empty when omitted.
+        assert_eq!(config.sensors[1].unit, "");
+        assert!(config.credentials.default.is_some());
+        assert!(config.credentials.hosts.contains_key("10.0.0.5"));
+    }
+
+    #[test]
+    fn collection_defaults_when_absent() {
+        let config: AppConfig = toml::from_str("sensors = []").expect("config parses");
+        assert_eq!(config.collection, default_collection());
+        assert!(config.sensors.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+/// A resolved BMC login.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Credential {
+    pub username: String,
+    pub password: String,
+}
+
+/// Credential configuration: an optional default block plus per-host overrides
+/// keyed by IP, so a mixed fleet of BMCs with different logins works in one run.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CredentialConfig {
+    pub default: Option<Credential>,
+    #[serde(default)]
+    pub hosts: HashMap<String, Credential>,
+}
+
+impl CredentialConfig {
+    /// Resolve the credential for a single host, in precedence order:
+    /// per-host config, default config block, `REDFISH_USERNAME`/
+    /// `REDFISH_PASSWORD` environment variables, and finally an interactive
+    /// prompt when stdin is a TTY (the password is never echoed).
+    pub fn resolve(&self, ip: &str) -> Result<Credential> {
+        if let Some(cred) = self.hosts.get(ip) {
+            return Ok(cred.clone());
+        }
+        if let Some(cred) = &self.default {
+            return Ok(cred.clone());
+        }
+        if let (Ok(username), Ok(password)) =
+            (env::var("REDFISH_USERNAME"), env::var("REDFISH_PASSWORD"))
+        {
+            return Ok(Credential { username, password });
+        }
+        if io::stdin().is_terminal() {
+            return prompt(ip);
+        }
+        bail!("no credentials configured for {ip} and stdin is not a TTY");
+    }
+
+    /// Resolve credentials for every host up front, before the TUI takes over
+    /// the terminal, so any interactive prompts happen on the plain console.
+    pub fn resolve_all(&self, ips: &[String]) -> Result<HashMap<String, Credential>> {
+        let mut resolved = HashMap::new();
+        for ip in ips {
+            if !resolved.contains_key(ip) {
+                resolved.insert(ip.clone(), self.resolve(ip)?);
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cred(username: &str) -> Credential {
+        Credential {
+            username: username.to_string(),
+            password: "pw".to_string(),
+        }
+    }
+
+    #[test]
+    fn per_host_overrides_default() {
+        let mut hosts = HashMap::new();
+        hosts.insert("10.0.0.5".to_string(), cred("operator"));
+        let config = CredentialConfig {
+            default: Some(cred("admin")),
+            hosts,
+        };
+        assert_eq!(config.resolve("10.0.0.5").unwrap().username, "operator");
+    }
+
+    #[test]
+    fn falls_back_to_default_block() {
+        let config = CredentialConfig {
+            default: Some(cred("admin")),
+            hosts: HashMap::new(),
+        };
+        assert_eq!(config.resolve("10.0.0.9").unwrap().username, "admin");
+    }
+}
+
+/// Prompt for a username (echoed) and password (hidden) on the TTY.
+fn prompt(ip: &str) -> Result<Credential> {
+    print!("Username for {ip}: ");
+    io::stdout().flush()?;
+    let mut username = String::new();
+    io::stdin().read_line(&mut username)?;
+    let password = rpassword::prompt_password(format!("Password for {ip}: "))?;
+    Ok(Credential {
+        username: username.trim().to_string(),
+        password,
+    })
+}
@@ -0,0 +1,263 @@
+use crate::{ConnectionState, SensorReading};
+use crate::args::OutputFormat;
+use crate::config::AppConfig;
+use anyhow::Result;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+    time::sleep,
+};
+
+type Readings = Arc<RwLock<HashMap<String, SensorReading>>>;
+type States = Arc<RwLock<HashMap<String, ConnectionState>>>;
+
+/// Run the tool without the TUI, emitting readings in the chosen format.
+///
+/// JSON and CSV stream one record per poll to stdout; Prometheus instead
+/// serves the current readings at `/metrics` for scraping.
+pub async fn run(
+    ips: &[String],
+    config: &AppConfig,
+    readings: Readings,
+    states: States,
+    format: OutputFormat,
+    port: u16,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => stream_json(ips, readings, states).await,
+        OutputFormat::Csv => stream_csv(ips, config, readings, states).await,
+        OutputFormat::Prometheus => serve_prometheus(readings, states, port).await,
+    }
+}
+
+/// Lowercase connection-state name for the headless `state` field, so JSON/CSV
+/// consumers can tell a live reading from a frozen one the way `redfish_up`
+/// does for the Prometheus path.
+fn state_label(state: Option<&ConnectionState>) -> &'static str {
+    match state {
+        Some(ConnectionState::Connected) => "connected",
+        Some(ConnectionState::Reauthenticating) => "reauthenticating",
+        Some(ConnectionState::Unreachable) => "unreachable",
+        None => "unknown",
+    }
+}
+
+/// Escape a string for use as a Prometheus label value: backslash, double
+/// quote, and newline are the three characters the text format requires.
+fn escape_label(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break,
+/// doubling any embedded quotes per RFC 4180.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn stream_json(ips: &[String], readings: Readings, states: States) -> Result<()> {
+    loop {
+        {
+            let data = readings.read().await;
+            let state_map = states.read().await;
+            let ts = unix_secs();
+            for ip in ips {
+                if let Some(reading) = data.get(ip) {
+                    let sensors: serde_json::Map<String, serde_json::Value> = reading
+                        .iter()
+                        .map(|(label, value)| {
+                            (label.clone(), serde_json::json!(value))
+                        })
+                        .collect();
+                    let state = state_map.get(ip);
+                    let record = serde_json::json!({
+                        "timestamp": ts,
+                        "host": ip,
+                        "up": matches!(state, Some(ConnectionState::Connected)),
+                        "state": state_label(state),
+                        "sensors": sensors,
+                    });
+                    println!("{record}");
+                }
+            }
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn stream_csv(
+    ips: &[String],
+    config: &AppConfig,
+    readings: Readings,
+    states: States,
+) -> Result<()> {
+    let header: Vec<String> = ["timestamp", "host", "state", "up"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(config.sensors.iter().map(|s| csv_field(&s.label)))
+        .collect();
+    println!("{}", header.join(","));
+
+    loop {
+        {
+            let data = readings.read().await;
+            let state_map = states.read().await;
+            let ts = unix_secs();
+            for ip in ips {
+                if let Some(reading) = data.get(ip) {
+                    let state = state_map.get(ip);
+                    let up = matches!(state, Some(ConnectionState::Connected)) as u8;
+                    let mut row = vec![
+                        ts.to_string(),
+                        csv_field(ip),
+                        state_label(state).to_string(),
+                        up.to_string(),
+                    ];
+                    row.extend(reading.iter().map(|(_, value)| {
+                        value.map(|v| v.to_string()).unwrap_or_default()
+                    }));
+                    println!("{}", row.join(","));
+                }
+            }
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn serve_prometheus(readings: Readings, states: States, port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    log::info!("serving Prometheus metrics on :{port}/metrics");
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let body = render_metrics(&readings, &states).await;
+        if let Err(e) = respond(stream, &body).await {
+            log::warn!("failed to serve /metrics request: {e}");
+        }
+    }
+}
+
+/// Render the current readings as Prometheus text-format gauges.
+async fn render_metrics(readings: &Readings, states: &States) -> String {
+    let data = readings.read().await;
+    let state_map = states.read().await;
+    let mut out = String::new();
+
+    out.push_str("# HELP redfish_sensor Latest Redfish sensor reading.\n");
+    out.push_str("# TYPE redfish_sensor gauge\n");
+    for (ip, reading) in data.iter() {
+        for (label, value) in reading.iter() {
+            if let Some(v) = value {
+                out.push_str(&format!(
+                    "redfish_sensor{{host=\"{}\",sensor=\"{}\"}} {v}\n",
+                    escape_label(ip),
+                    escape_label(label)
+                ));
+            }
+        }
+    }
+
+    out.push_str("# HELP redfish_up Whether the host was reachable on the last poll.\n");
+    out.push_str("# TYPE redfish_up gauge\n");
+    let hosts: std::collections::BTreeSet<&String> =
+        data.keys().chain(state_map.keys()).collect();
+    for ip in hosts {
+        let up = matches!(state_map.get(ip), Some(ConnectionState::Connected)) as u8;
+        out.push_str(&format!("redfish_up{{host=\"{}\"}} {up}\n", escape_label(ip)));
+    }
+
+    out
+}
+
+async fn respond(mut stream: TcpStream, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_label_handles_quotes_backslash_newline() {
+        assert_eq!(escape_label("Inlet \"A\""), "Inlet \\\"A\\\"");
+        assert_eq!(escape_label("a\\b"), "a\\\\b");
+        assert_eq!(escape_label("line\none"), "line\\none");
+    }
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn state_label_covers_every_state() {
+        assert_eq!(state_label(Some(&ConnectionState::Connected)), "connected");
+        assert_eq!(
+            state_label(Some(&ConnectionState::Reauthenticating)),
+            "reauthenticating"
+        );
+        assert_eq!(
+            state_label(Some(&ConnectionState::Unreachable)),
+            "unreachable"
+        );
+        assert_eq!(state_label(None), "unknown");
+    }
+
+    #[tokio::test]
+    async fn render_metrics_emits_gauges_and_up_flag() {
+        let readings: Readings = Arc::new(RwLock::new(HashMap::new()));
+        let states: States = Arc::new(RwLock::new(HashMap::new()));
+        readings.write().await.insert(
+            "10.0.0.5".to_string(),
+            vec![
+                ("PSU".to_string(), Some(120.5)),
+                ("Fan".to_string(), None),
+            ],
+        );
+        states
+            .write()
+            .await
+            .insert("10.0.0.5".to_string(), ConnectionState::Connected);
+
+        let out = render_metrics(&readings, &states).await;
+        assert!(out.contains("# TYPE redfish_sensor gauge"));
+        assert!(out.contains("redfish_sensor{host=\"10.0.0.5\",sensor=\"PSU\"} 120.5"));
+        // A `None` reading is omitted rather than exported as 0.
+        assert!(!out.contains("sensor=\"Fan\""));
+        assert!(out.contains("redfish_up{host=\"10.0.0.5\"} 1"));
+    }
+}
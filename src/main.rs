@@ -1,144 +1,480 @@
 mod args;
+mod config;
+mod credentials;
+mod export;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Parser;
+use config::AppConfig;
+use credentials::Credential;
+use log::LevelFilter;
+use simplelog::{Config as LogConfig, WriteLogger};
+use std::fs::File;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use rand::Rng;
 use ratatui::{prelude::*, widgets::*};
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde_json::{Value, from_str, json};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     io,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::{sync::RwLock, time::sleep};
 
-#[derive(Debug, Clone)]
-struct SensorReading {
-    psu_pin: Option<u64>,
-    cpu_power: Option<u64>,
-    cpu0_power: Option<u64>,
-    cpu1_power: Option<u64>,
-    fan_power: Option<u64>,
-    cpu0_temp: Option<u64>,
-    cpu1_temp: Option<u64>,
+/// Latest value of every configured sensor for a host, in config order.
+///
+/// Each entry pairs the sensor's display label with its most recent reading,
+/// or `None` if that sensor was absent from the last `Sensors[]` response.
+pub(crate) type SensorReading = Vec<(String, Option<f64>)>;
+
+/// Rolling sample history for a host: one bounded buffer per configured
+/// sensor, in config order, holding up to [`HISTORY_LEN`] recent readings.
+type History = Vec<VecDeque<f64>>;
+
+/// Number of samples retained per metric for the graph view.
+const HISTORY_LEN: usize = 300;
+
+/// Which panel layout `start_ui` is currently rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    /// The instantaneous numeric readout for every sensor.
+    Numeric,
+    /// A sparkline of one metric's recent history for the selected host.
+    Graph,
+}
+
+/// Why a host's panel does or does not currently hold data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConnectionState {
+    /// Polling succeeded on the last tick.
+    Connected,
+    /// The session expired or was rejected; a new login is in flight.
+    Reauthenticating,
+    /// The host is erroring or unreachable and is being retried with backoff.
+    Unreachable,
+}
+
+/// Reason a single `Sensors[]` poll failed.
+enum FetchError {
+    /// The BMC rejected the session token (401); the caller should re-login.
+    Unauthorized,
+    /// The request failed, timed out, or returned an unusable body.
+    Unreachable,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = args::Args::parse();
+    init_logging(&args)?;
+    let config = Arc::new(AppConfig::load(&args.config).await?);
     let client = Client::builder()
         .danger_accept_invalid_certs(true)
         .build()?;
 
-    let tokens = get_tokens(&client, &args.ips).await?;
+    // Resolve every host's credentials before the TUI grabs the terminal, so
+    // any interactive password prompt happens on the plain console.
+    let credentials = config.credentials.resolve_all(&args.ips)?;
+
     let readings = Arc::new(RwLock::new(HashMap::<String, SensorReading>::new()));
+    let states = Arc::new(RwLock::new(HashMap::<String, ConnectionState>::new()));
+    let history = Arc::new(RwLock::new(HashMap::<String, History>::new()));
+
+    // One supervisor task per host: each logs in, polls, and independently
+    // re-authenticates and backs off so a single flaky BMC cannot stall the
+    // others or drop off the display forever.
+    for ip in &args.ips {
+        let credential = credentials[ip].clone();
+        tokio::spawn(poll_host(
+            client.clone(),
+            ip.clone(),
+            credential,
+            Arc::clone(&config),
+            Arc::clone(&readings),
+            Arc::clone(&states),
+            Arc::clone(&history),
+        ));
+    }
+
+    if args.headless() {
+        export::run(&args.ips, &config, readings, states, args.format(), args.port).await
+    } else {
+        start_ui(&args.ips, &config, readings, states, history).await
+    }
+}
+
+/// Wire up file logging when `--log-to` is given, at a level set by `-v`.
+fn init_logging(args: &args::Args) -> Result<()> {
+    if let Some(path) = &args.log_to {
+        let level = match args.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        WriteLogger::init(level, LogConfig::default(), File::create(path)?)?;
+    }
+    Ok(())
+}
 
-    let read_ips = args.ips.clone();
-    let client_clone = client.clone();
-    let tokens_clone = tokens.clone();
-    let readings_clone = Arc::clone(&readings);
+/// Supervise a single host forever: keep a valid session, poll its sensors,
+/// and on failure re-authenticate or back off with jitter before retrying.
+async fn poll_host(
+    client: Client,
+    ip: String,
+    credential: Credential,
+    config: Arc<AppConfig>,
+    readings: Arc<RwLock<HashMap<String, SensorReading>>>,
+    states: Arc<RwLock<HashMap<String, ConnectionState>>>,
+    history: Arc<RwLock<HashMap<String, History>>>,
+) {
+    const BASE_DELAY: Duration = Duration::from_secs(1);
+    const MAX_DELAY: Duration = Duration::from_secs(30);
+
+    let mut token: Option<String> = None;
+    let mut delay = BASE_DELAY;
 
-    tokio::spawn(async move {
-        loop {
-            update_readings(&client_clone, &read_ips, &tokens_clone, &readings_clone).await;
-            sleep(Duration::from_secs(1)).await;
+    loop {
+        if token.is_none() {
+            set_state(&states, &ip, ConnectionState::Reauthenticating).await;
+            match login(&client, &ip, &credential).await {
+                Ok(t) => {
+                    log::info!("{ip}: session established");
+                    token = Some(t);
+                }
+                Err(e) => {
+                    log::warn!("{ip}: login failed: {e}");
+                    set_state(&states, &ip, ConnectionState::Unreachable).await;
+                    backoff(&mut delay, MAX_DELAY).await;
+                    continue;
+                }
+            }
         }
-    });
 
-    start_ui(&args.ips, readings).await
+        match fetch_sensors(&client, &ip, token.as_deref().unwrap(), &config).await {
+            Ok(reading) => {
+                {
+                    let mut hist = history.write().await;
+                    let buffers = hist
+                        .entry(ip.clone())
+                        .or_insert_with(|| vec![VecDeque::new(); reading.len()]);
+                    for (buffer, (_, value)) in buffers.iter_mut().zip(reading.iter()) {
+                        // Only record present samples: pushing 0.0 for a missing
+                        // sensor would skew the window's min/avg and draw a
+                        // phantom dip to zero in the sparkline.
+                        if let Some(v) = value {
+                            buffer.push_back(*v);
+                            if buffer.len() > HISTORY_LEN {
+                                buffer.pop_front();
+                            }
+                        }
+                    }
+                }
+                readings.write().await.insert(ip.clone(), reading);
+                set_state(&states, &ip, ConnectionState::Connected).await;
+                delay = BASE_DELAY;
+                sleep(BASE_DELAY).await;
+            }
+            Err(FetchError::Unauthorized) => {
+                // Session expired: drop the token and re-login next iteration.
+                // Back off so a token the BMC keeps rejecting cannot turn into
+                // a re-auth hot loop; a successful poll resets the delay.
+                log::info!("{ip}: session expired, re-authenticating");
+                token = None;
+                set_state(&states, &ip, ConnectionState::Reauthenticating).await;
+                backoff(&mut delay, MAX_DELAY).await;
+            }
+            Err(FetchError::Unreachable) => {
+                log::warn!("{ip}: poll failed, backing off for {:?}", delay);
+                set_state(&states, &ip, ConnectionState::Unreachable).await;
+                backoff(&mut delay, MAX_DELAY).await;
+            }
+        }
+    }
+}
+
+/// Sleep for the current delay, then double it (capped) with a little jitter
+/// so a fleet of hosts that failed together does not retry in lockstep.
+async fn backoff(delay: &mut Duration, max: Duration) {
+    let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=500));
+    sleep(*delay + jitter).await;
+    *delay = (*delay * 2).min(max);
 }
 
-async fn get_tokens(client: &Client, ips: &[String]) -> Result<Vec<String>> {
+async fn set_state(
+    states: &Arc<RwLock<HashMap<String, ConnectionState>>>,
+    ip: &str,
+    state: ConnectionState,
+) {
+    states.write().await.insert(ip.to_string(), state);
+}
+
+/// Open a Redfish session against `ip` and return its auth token.
+async fn login(client: &Client, ip: &str, credential: &Credential) -> Result<String> {
     let login_json = json!({
-        "UserName": "admin",
-        "Password": "admin"
+        "UserName": credential.username,
+        "Password": credential.password
     });
 
-    let mut tokens = Vec::new();
+    let login_url = format!("https://{}/redfish/v1/SessionService/Sessions", ip);
+    let resp = client
+        .post(&login_url)
+        .header("Content-Type", "application/json")
+        .json(&login_json)
+        .send()
+        .await?;
 
-    for ip in ips {
-        let login_url = format!("https://{}/redfish/v1/SessionService/Sessions", ip);
-        let resp = client
-            .post(&login_url)
-            .header("Content-Type", "application/json")
-            .json(&login_json)
-            .send()
-            .await?;
+    let text = resp.text().await?;
+    let json: Value = from_str(&text)?;
+    let token = json["Oem"]["Public"]["X-Auth-Token"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    // A BMC that rejects the login (wrong password, non-2xx) still returns a
+    // parseable body without a token; treat that as a failure so the caller
+    // backs off instead of hot-looping on a perpetually empty token.
+    if token.is_empty() {
+        bail!("no auth token in login response");
+    }
+    Ok(token)
+}
+
+/// Poll the configured sensor collection once and map it through the config.
+async fn fetch_sensors(
+    client: &Client,
+    ip: &str,
+    token: &str,
+    config: &AppConfig,
+) -> std::result::Result<SensorReading, FetchError> {
+    let sensor_url = format!("https://{}{}", ip, config.collection);
+    let resp = client
+        .get(sensor_url)
+        .header("X-Auth-Token", token)
+        .send()
+        .await
+        .map_err(|_| FetchError::Unreachable)?;
 
-        let text = resp.text().await?;
-        let json: Value = from_str(&text)?;
-        let token = json["Oem"]["Public"]["X-Auth-Token"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        tokens.push(token);
+    if resp.status() == StatusCode::UNAUTHORIZED {
+        return Err(FetchError::Unauthorized);
     }
+    if !resp.status().is_success() {
+        return Err(FetchError::Unreachable);
+    }
+
+    let text = resp.text().await.map_err(|_| FetchError::Unreachable)?;
+    let json: Value = from_str(&text).map_err(|e| {
+        log::debug!("{ip}: failed to parse sensor response: {e}");
+        FetchError::Unreachable
+    })?;
+    let sensors = json
+        .get("Sensors")
+        .and_then(|s| s.as_array())
+        .ok_or(FetchError::Unreachable)?;
 
-    Ok(tokens)
+    Ok(map_readings(config, sensors))
 }
 
-async fn update_readings(
-    client: &Client,
-    ips: &[String],
-    tokens: &[String],
-    readings: &Arc<RwLock<HashMap<String, SensorReading>>>,
+/// Map a Redfish `Sensors[]` array through the configured sensor list,
+/// producing one labelled reading per config entry in order. A configured
+/// sensor missing from the response yields `None` rather than dropping the row.
+fn map_readings(config: &AppConfig, sensors: &[Value]) -> SensorReading {
+    config
+        .sensors
+        .iter()
+        .map(|cfg| {
+            let value = sensors
+                .iter()
+                .find(|s| s.get("Name").and_then(|v| v.as_str()) == Some(cfg.name.as_str()))
+                .and_then(|s| s.get("Reading"))
+                .and_then(|v| v.as_f64());
+            (cfg.label.clone(), value)
+        })
+        .collect()
+}
+
+/// Build the instantaneous per-sensor readout for a host, falling back to the
+/// connection state when no reading is available yet.
+fn numeric_panel(
+    config: &AppConfig,
+    data: &HashMap<String, SensorReading>,
+    state_map: &HashMap<String, ConnectionState>,
+    ip: &str,
+) -> String {
+    match data.get(ip) {
+        Some(reading) => reading
+            .iter()
+            .zip(config.sensors.iter())
+            .map(|((label, value), cfg)| match value {
+                Some(v) => format!(" {}: {} {}", label, v, cfg.unit),
+                None => format!(" {}: —", label),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => match state_map.get(ip) {
+            Some(ConnectionState::Reauthenticating) => " Re-authenticating…".to_string(),
+            Some(ConnectionState::Unreachable) => " Unreachable — retrying.".to_string(),
+            _ => " No data available.".to_string(),
+        },
+    }
+}
+
+/// Render a sparkline of one metric's history for a host, titled with the
+/// host, metric label, and min/max/avg over the retained window.
+fn render_graph(
+    f: &mut Frame,
+    area: Rect,
+    config: &AppConfig,
+    history: &HashMap<String, History>,
+    ip: &str,
+    metric: usize,
 ) {
-    let mut map = HashMap::new();
-
-    for (ip, token) in ips.iter().zip(tokens.iter()) {
-        let sensor_url = format!("https://{}/redfish/v1/Chassis/1/ThresholdSensors", ip);
-        if let Ok(resp) = client
-            .get(sensor_url)
-            .header("X-Auth-Token", token)
-            .send()
-            .await
-        {
-            if let Ok(json) = from_str::<Value>(&resp.text().await.unwrap_or_default()) {
-                if let Some(sensors) = json.get("Sensors").and_then(|s| s.as_array()) {
-                    let mut reading = SensorReading {
-                        psu_pin: None,
-                        cpu_power: None,
-                        cpu0_power: None,
-                        cpu1_power: None,
-                        cpu0_temp: None,
-                        cpu1_temp: None,
-                        fan_power: None,
-                    };
-
-                    for sensor in sensors {
-                        let name = sensor.get("Name").and_then(|v| v.as_str()).unwrap_or("");
-                        let value = sensor.get("Reading").and_then(|v| v.as_u64());
-                        match name {
-                            "PSU1_PIN" => reading.psu_pin = value,
-                            "CPU_Power" => reading.cpu_power = value,
-                            "CPU0_Power" => reading.cpu0_power = value,
-                            "CPU1_Power" => reading.cpu1_power = value,
-                            "CPU0_Temp" => reading.cpu0_temp = value,
-                            "CPU1_Temp" => reading.cpu1_temp = value,
-                            "Fan_Power" => reading.fan_power = value,
-                            _ => {}
-                        }
-                    }
+    let label = config
+        .sensors
+        .get(metric)
+        .map(|c| c.label.as_str())
+        .unwrap_or("");
+    let samples = history.get(ip).and_then(|buffers| buffers.get(metric));
 
-                    map.insert(ip.clone(), reading);
-                }
-            }
+    let title = match samples {
+        Some(buffer) if !buffer.is_empty() => {
+            let min = buffer.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = buffer.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let avg = buffer.iter().sum::<f64>() / buffer.len() as f64;
+            format!("{ip} — {label} (min {min:.1} / max {max:.1} / avg {avg:.1})")
         }
+        _ => format!("{ip} — {label}"),
+    };
+
+    let data: Vec<u64> = samples
+        .map(|buffer| buffer.iter().map(|v| v.round() as u64).collect())
+        .unwrap_or_default();
+
+    let sparkline = Sparkline::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .data(&data);
+    f.render_widget(sparkline, area);
+}
+
+/// Short label for a host's connection state, shown in the host table.
+fn state_label(state: Option<&ConnectionState>) -> &'static str {
+    match state {
+        Some(ConnectionState::Connected) => "OK",
+        Some(ConnectionState::Reauthenticating) => "auth…",
+        Some(ConnectionState::Unreachable) => "down",
+        None => "—",
+    }
+}
+
+/// The hosts to display, after applying the substring `filter` and, when a
+/// `sort_metric` is set, ordering by that metric's latest reading descending
+/// (hosts with no reading sort last). With no sort, argument order is
+/// preserved.
+fn visible_hosts(
+    ips: &[String],
+    data: &HashMap<String, SensorReading>,
+    filter: &str,
+    sort_metric: Option<usize>,
+) -> Vec<String> {
+    let mut hosts: Vec<String> = ips
+        .iter()
+        .filter(|ip| filter.is_empty() || ip.contains(filter))
+        .cloned()
+        .collect();
+
+    if let Some(metric) = sort_metric {
+        hosts.sort_by(|a, b| {
+            let value = |ip: &str| {
+                data.get(ip)
+                    .and_then(|r| r.get(metric))
+                    .and_then(|(_, v)| *v)
+            };
+            value(b)
+                .partial_cmp(&value(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
     }
 
-    let mut guard = readings.write().await;
-    *guard = map;
+    hosts
+}
+
+/// Render the scrollable host table: one row per visible host with its
+/// connection state and the latest value of every configured sensor.
+fn render_host_table(
+    f: &mut Frame,
+    area: Rect,
+    config: &AppConfig,
+    data: &HashMap<String, SensorReading>,
+    state_map: &HashMap<String, ConnectionState>,
+    hosts: &[String],
+    table_state: &mut TableState,
+) {
+    let mut header = vec!["Host".to_string(), "State".to_string()];
+    header.extend(config.sensors.iter().map(|s| s.label.clone()));
+    let header = Row::new(header).style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = hosts.iter().map(|ip| {
+        let mut cells = vec![ip.clone(), state_label(state_map.get(ip)).to_string()];
+        for (i, cfg) in config.sensors.iter().enumerate() {
+            let cell = data
+                .get(ip)
+                .and_then(|r| r.get(i))
+                .and_then(|(_, v)| *v)
+                .map(|v| format!("{v} {}", cfg.unit))
+                .unwrap_or_else(|| "—".to_string());
+            cells.push(cell);
+        }
+        Row::new(cells)
+    });
+
+    let mut widths = vec![Constraint::Min(16), Constraint::Length(7)];
+    widths.extend(std::iter::repeat(Constraint::Min(8)).take(config.sensors.len()));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().title("Hosts").borders(Borders::ALL))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(table, area, table_state);
+}
+
+/// Render the full-screen detail panel for a single host: its numeric readout
+/// for every configured sensor above a sparkline of the current metric.
+fn render_detail(
+    f: &mut Frame,
+    area: Rect,
+    config: &AppConfig,
+    data: &HashMap<String, SensorReading>,
+    state_map: &HashMap<String, ConnectionState>,
+    history: &HashMap<String, History>,
+    ip: &str,
+    metric: usize,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(10)])
+        .split(area);
+
+    let text = numeric_panel(config, data, state_map, ip);
+    let panel = Paragraph::new(text).block(
+        Block::default()
+            .title(format!("{ip} — sensors"))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(panel, chunks[0]);
+    render_graph(f, chunks[1], config, history, ip, metric);
 }
 
 async fn start_ui(
     ips: &[String],
+    config: &AppConfig,
     readings: Arc<RwLock<HashMap<String, SensorReading>>>,
+    states: Arc<RwLock<HashMap<String, ConnectionState>>>,
+    history: Arc<RwLock<HashMap<String, History>>>,
 ) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -149,41 +485,137 @@ async fn start_ui(
     let tick_rate = Duration::from_millis(1000);
     let mut last_tick = Instant::now();
 
+    let mut view = View::Numeric;
+    let mut metric = 0usize;
+    let mut table_state = TableState::default();
+    table_state.select(if ips.is_empty() { None } else { Some(0) });
+    // Host whose full-screen detail panel is open, if any.
+    let mut detail: Option<String> = None;
+    // Index of the metric hosts are sorted by, or `None` for argument order.
+    let mut sort_metric: Option<usize> = None;
+    // Substring filter applied to host names; `filtering` is the edit mode.
+    let mut filter = String::new();
+    let mut filtering = false;
+
     loop {
         let data = readings.read().await;
+        let state_map = states.read().await;
+        let hist = history.read().await;
+
+        let hosts = visible_hosts(ips, &data, &filter, sort_metric);
+        // Keep the selection within the (possibly filtered) host list.
+        let selected = match table_state.selected() {
+            Some(_) if hosts.is_empty() => None,
+            Some(i) => Some(i.min(hosts.len() - 1)),
+            None if hosts.is_empty() => None,
+            None => Some(0),
+        };
+        table_state.select(selected);
+
         terminal.draw(|f| {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .margin(1)
-                .constraints(vec![Constraint::Min(3); ips.len()])
+                .constraints([Constraint::Min(3), Constraint::Length(1)])
                 .split(f.area());
 
-            for (i, ip) in ips.iter().enumerate() {
-                let reading = data.get(ip);
-                let text = match reading {
-                    Some(r) => format!(
-                        " PSU_PIN: {} W | CPU Tot: {} W  \t\t CPU 0: {} W | CPU 1: {} W \n Fan: {} W \t\t CPU 0 Temp: {} °C | CPU 1 Temp: {} °C",
-                        r.psu_pin.unwrap_or(0),
-                        r.cpu_power.unwrap_or(0),
-                        r.cpu0_power.unwrap_or(0),
-                        r.cpu1_power.unwrap_or(0),
-                        r.fan_power.unwrap_or(0),
-                        r.cpu0_temp.unwrap_or(0),
-                        r.cpu1_temp.unwrap_or(0),
+            if let Some(ip) = detail.as_deref() {
+                render_detail(f, chunks[0], config, &data, &state_map, &hist, ip, metric);
+            } else {
+                match view {
+                    View::Numeric => render_host_table(
+                        f,
+                        chunks[0],
+                        config,
+                        &data,
+                        &state_map,
+                        &hosts,
+                        &mut table_state,
                     ),
-                    None => " No data available.".to_string(),
-                };
-
-                let block = Paragraph::new(text)
-                    .block(Block::default().title(ip.to_owned()).borders(Borders::ALL));
-                f.render_widget(block, chunks[i]);
+                    View::Graph => {
+                        // Graph the selected host only, so the view scales with
+                        // the table selection instead of stacking every host.
+                        if let Some(ip) = selected.and_then(|i| hosts.get(i)) {
+                            render_graph(f, chunks[0], config, &hist, ip, metric);
+                        }
+                    }
+                }
             }
+
+            let status = if filtering {
+                format!(" filter: {}_", filter)
+            } else {
+                let sort = sort_metric
+                    .and_then(|m| config.sensors.get(m))
+                    .map(|s| s.label.as_str())
+                    .unwrap_or("none");
+                format!(
+                    " ↑/↓ select  enter detail  g graph  m metric  s sort ({sort})  / filter  q quit"
+                )
+            };
+            f.render_widget(Paragraph::new(status), chunks[1]);
         })?;
 
-        if event::poll(tick_rate - last_tick.elapsed())? {
+        if event::poll(tick_rate.saturating_sub(last_tick.elapsed()))? {
             if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
+                if filtering {
+                    match key.code {
+                        KeyCode::Enter | KeyCode::Esc => filtering = false,
+                        KeyCode::Backspace => {
+                            filter.pop();
+                        }
+                        KeyCode::Char(c) => filter.push(c),
+                        _ => {}
+                    }
+                } else if detail.is_some() {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => detail = None,
+                        KeyCode::Char('m') if !config.sensors.is_empty() => {
+                            metric = (metric + 1) % config.sensors.len();
+                        }
+                        _ => {}
+                    }
+                } else {
+                    match key.code {
+                        KeyCode::Char('q') => break,
+                        KeyCode::Down => {
+                            if !hosts.is_empty() {
+                                let next = selected.map_or(0, |i| (i + 1) % hosts.len());
+                                table_state.select(Some(next));
+                            }
+                        }
+                        KeyCode::Up => {
+                            if !hosts.is_empty() {
+                                let prev = selected
+                                    .map_or(0, |i| (i + hosts.len() - 1) % hosts.len());
+                                table_state.select(Some(prev));
+                            }
+                        }
+                        KeyCode::Enter => {
+                            detail = selected.and_then(|i| hosts.get(i).cloned());
+                        }
+                        KeyCode::Char('g') => {
+                            view = match view {
+                                View::Numeric => View::Graph,
+                                View::Graph => View::Numeric,
+                            };
+                        }
+                        KeyCode::Char('m') if !config.sensors.is_empty() => {
+                            metric = (metric + 1) % config.sensors.len();
+                        }
+                        KeyCode::Char('s') if !config.sensors.is_empty() => {
+                            sort_metric = match sort_metric {
+                                None => Some(0),
+                                Some(m) if m + 1 < config.sensors.len() => Some(m + 1),
+                                Some(_) => None,
+                            };
+                        }
+                        KeyCode::Char('/') => {
+                            filtering = true;
+                            filter.clear();
+                        }
+                        _ => {}
+                    }
                 }
             }
         }
@@ -202,3 +634,70 @@ async fn start_ui(
     terminal.show_cursor()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SensorConfig;
+    use serde_json::json;
+
+    fn config_with(sensors: &[(&str, &str)]) -> AppConfig {
+        AppConfig {
+            collection: "/redfish/v1/Chassis/1/Sensors".to_string(),
+            sensors: sensors
+                .iter()
+                .map(|(name, label)| SensorConfig {
+                    name: name.to_string(),
+                    label: label.to_string(),
+                    unit: "W".to_string(),
+                })
+                .collect(),
+            credentials: Default::default(),
+        }
+    }
+
+    #[test]
+    fn map_readings_matches_by_name_and_keeps_order() {
+        let config = config_with(&[("CPU0_Power", "CPU 0"), ("Fan_Power", "Fan")]);
+        let sensors = vec![
+            json!({ "Name": "Fan_Power", "Reading": 12.0 }),
+            json!({ "Name": "CPU0_Power", "Reading": 95.5 }),
+        ];
+        let reading = map_readings(&config, &sensors);
+        assert_eq!(
+            reading,
+            vec![
+                ("CPU 0".to_string(), Some(95.5)),
+                ("Fan".to_string(), Some(12.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn map_readings_reports_missing_sensor_as_none() {
+        let config = config_with(&[("CPU0_Power", "CPU 0"), ("CPU1_Power", "CPU 1")]);
+        let sensors = vec![json!({ "Name": "CPU0_Power", "Reading": 88.25 })];
+        let reading = map_readings(&config, &sensors);
+        assert_eq!(reading[0].1, Some(88.25));
+        assert_eq!(reading[1].1, None);
+    }
+
+    #[test]
+    fn visible_hosts_filters_by_substring() {
+        let data = HashMap::new();
+        let ips = vec!["10.0.0.5".to_string(), "10.0.1.7".to_string()];
+        let hosts = visible_hosts(&ips, &data, "0.1", None);
+        assert_eq!(hosts, vec!["10.0.1.7".to_string()]);
+    }
+
+    #[test]
+    fn visible_hosts_sorts_by_metric_descending_missing_last() {
+        let ips = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut data: HashMap<String, SensorReading> = HashMap::new();
+        data.insert("a".to_string(), vec![("P".to_string(), Some(10.0))]);
+        data.insert("b".to_string(), vec![("P".to_string(), Some(40.0))]);
+        // "c" has no reading and should sort last.
+        let hosts = visible_hosts(&ips, &data, "", Some(0));
+        assert_eq!(hosts, vec!["b".to_string(), "a".to_string(), "c".to_string()]);
+    }
+}